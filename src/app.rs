@@ -0,0 +1,410 @@
+use indexmap::IndexMap;
+use ratatui::widgets::ListState;
+use unicode_segmentation::UnicodeSegmentation;
+
+pub enum CurrentScreen {
+    Main,
+    Editing,
+    Exiting,
+    /// Shown instead of the usual Main screen when the file passed on the
+    /// command line exists but couldn't be read or parsed as a JSON object. The
+    /// message is held in `App::error_message`.
+    Error,
+}
+
+pub enum CurrentlyEditing {
+    Key,
+    Value,
+}
+
+/// How the text in `value_input` should be interpreted when the pair is
+/// saved. Cycled with Ctrl+T while editing the Value field.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ValueType {
+    Str,
+    Number,
+    Bool,
+    Null,
+    RawJson,
+}
+
+impl ValueType {
+    const ALL: [ValueType; 5] = [
+        ValueType::Str,
+        ValueType::Number,
+        ValueType::Bool,
+        ValueType::Null,
+        ValueType::RawJson,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ValueType::Str => "string",
+            ValueType::Number => "number",
+            ValueType::Bool => "boolean",
+            ValueType::Null => "null",
+            ValueType::RawJson => "raw json",
+        }
+    }
+
+    fn next(self) -> ValueType {
+        let position = Self::ALL.iter().position(|t| *t == self).unwrap_or(0);
+        Self::ALL[(position + 1) % Self::ALL.len()]
+    }
+
+    /// Parses `text` according to this type, the same conversion
+    /// `save_key_value` applies when committing the pair.
+    fn parse(self, text: &str) -> Result<serde_json::Value, String> {
+        match self {
+            ValueType::Str => Ok(serde_json::Value::String(text.to_string())),
+            ValueType::Number => text
+                .parse::<serde_json::Number>()
+                .map(serde_json::Value::Number)
+                .map_err(|_| format!("'{text}' is not a valid number")),
+            ValueType::Bool => match text {
+                "true" => Ok(serde_json::Value::Bool(true)),
+                "false" => Ok(serde_json::Value::Bool(false)),
+                _ => Err("boolean must be exactly 'true' or 'false'".to_string()),
+            },
+            ValueType::Null => {
+                if text.is_empty() || text == "null" {
+                    Ok(serde_json::Value::Null)
+                } else {
+                    Err("null takes no value, leave the field blank".to_string())
+                }
+            }
+            ValueType::RawJson => {
+                serde_json::from_str(text).map_err(|err| format!("invalid json: {err}"))
+            }
+        }
+    }
+
+    /// The [`ValueType`] that best matches an already-stored value, so
+    /// re-opening the popup for editing shows the type it was saved as.
+    fn from_value(value: &serde_json::Value) -> ValueType {
+        match value {
+            serde_json::Value::String(_) => ValueType::Str,
+            serde_json::Value::Number(_) => ValueType::Number,
+            serde_json::Value::Bool(_) => ValueType::Bool,
+            serde_json::Value::Null => ValueType::Null,
+            serde_json::Value::Array(_) | serde_json::Value::Object(_) => ValueType::RawJson,
+        }
+    }
+}
+
+pub struct App {
+    pub key_input: String,
+    pub value_input: String,
+    /// Cursor position in the currently active input field, counted in
+    /// grapheme clusters rather than bytes or chars so that multi-codepoint
+    /// emoji and other combined clusters move and delete as a single unit.
+    pub cursor_position: usize,
+    /// Order-preserving so the Main screen list stays in insertion order
+    /// across edits instead of reshuffling every time a pair is saved.
+    pub pairs: IndexMap<String, serde_json::Value>,
+    pub current_screen: CurrentScreen,
+    pub currently_editing: Option<CurrentlyEditing>,
+    pub error_message: Option<String>,
+    /// The type `value_input` is currently being interpreted as.
+    pub value_type: ValueType,
+    /// Set when `value_input` doesn't parse as `value_type`; shown in the
+    /// popup footer and blocks saving until cleared.
+    pub value_error: Option<String>,
+    pub list_state: ListState,
+    /// The key of the pair being overwritten when the popup was opened via
+    /// `begin_edit_selected`, so `save_key_value` knows to replace it rather
+    /// than insert a new one. `None` means the popup was opened for a new
+    /// pair.
+    editing_key: Option<String>,
+}
+
+impl App {
+    pub fn init() -> App {
+        App {
+            key_input: String::new(),
+            value_input: String::new(),
+            cursor_position: 0,
+            pairs: IndexMap::new(),
+            current_screen: CurrentScreen::Main,
+            currently_editing: None,
+            error_message: None,
+            value_type: ValueType::Str,
+            value_error: None,
+            list_state: ListState::default(),
+            editing_key: None,
+        }
+    }
+
+    /// Builds an `App` pre-populated with pairs loaded from an existing
+    /// document, so the Main screen list starts with them instead of blank.
+    pub fn with_pairs(pairs: IndexMap<String, serde_json::Value>) -> App {
+        App {
+            pairs,
+            ..App::init()
+        }
+    }
+
+    /// Builds an `App` parked on the Error screen, e.g. because the file
+    /// passed on the command line couldn't be parsed as JSON.
+    pub fn with_error(message: String) -> App {
+        App {
+            current_screen: CurrentScreen::Error,
+            error_message: Some(message),
+            ..App::init()
+        }
+    }
+
+    /// Commits the current key/value pair if the value parses under the
+    /// active `value_type`. Returns `false` (and leaves `value_error` set)
+    /// without saving if it doesn't, so callers should only advance past
+    /// the Editing screen when this returns `true`.
+    pub fn save_key_value(&mut self) -> bool {
+        let value = match self.value_type.parse(&self.value_input) {
+            Ok(value) => value,
+            Err(error) => {
+                self.value_error = Some(error);
+                return false;
+            }
+        };
+
+        // If the key was renamed while editing an existing pair, remove the
+        // old entry and reinsert at its original index, so the rename
+        // doesn't reshuffle the rest of the list to the end. A plain
+        // `insert` after `shift_remove` would instead append the renamed
+        // pair last, since as far as the map is concerned it's a brand new
+        // key.
+        match self.editing_key.take() {
+            Some(old_key) if old_key != self.key_input => {
+                let index = self.pairs.shift_remove_full(&old_key).map(|(index, ..)| index);
+                let index = index.unwrap_or(self.pairs.len());
+                self.pairs.shift_insert(index, self.key_input.clone(), value);
+            }
+            _ => {
+                self.pairs.insert(self.key_input.clone(), value);
+            }
+        }
+
+        self.key_input = String::new();
+        self.value_input = String::new();
+        self.cursor_position = 0;
+        self.currently_editing = None;
+        self.value_type = ValueType::Str;
+        self.value_error = None;
+        true
+    }
+
+    /// Opens the editing popup blank, for adding a new pair.
+    pub fn begin_new_pair(&mut self) {
+        self.key_input = String::new();
+        self.value_input = String::new();
+        self.editing_key = None;
+        self.value_type = ValueType::Str;
+        self.value_error = None;
+        self.current_screen = CurrentScreen::Editing;
+        self.currently_editing = Some(CurrentlyEditing::Key);
+        self.cursor_position = 0;
+    }
+
+    /// Opens the editing popup pre-filled with the selected pair's key and
+    /// value, so saving overwrites it in place.
+    pub fn begin_edit_selected(&mut self) {
+        let Some((key, value)) = self
+            .list_state
+            .selected()
+            .and_then(|index| self.pairs.get_index(index))
+        else {
+            self.begin_new_pair();
+            return;
+        };
+
+        self.key_input = key.clone();
+        self.value_type = ValueType::from_value(value);
+        self.value_input = match value {
+            serde_json::Value::String(s) => s.clone(),
+            serde_json::Value::Null => String::new(),
+            other => other.to_string(),
+        };
+        self.value_error = None;
+        self.editing_key = Some(key.clone());
+        self.current_screen = CurrentScreen::Editing;
+        self.currently_editing = Some(CurrentlyEditing::Key);
+        self.cursor_position = self.active_input_len();
+    }
+
+    /// Cycles the active value type (string -> number -> boolean -> null ->
+    /// raw json -> string) and re-validates the current text against it.
+    pub fn cycle_value_type(&mut self) {
+        self.value_type = self.value_type.next();
+        self.revalidate_value();
+    }
+
+    /// Re-checks `value_input` against `value_type`, updating `value_error`.
+    /// Called after every edit to the Value field so the footer's
+    /// validation message stays live as the user types.
+    fn revalidate_value(&mut self) {
+        self.value_error = self.value_type.parse(&self.value_input).err();
+    }
+
+    /// Moves the highlighted selection down, wrapping to the top.
+    pub fn select_next(&mut self) {
+        if self.pairs.is_empty() {
+            return;
+        }
+        let next = match self.list_state.selected() {
+            Some(i) if i + 1 < self.pairs.len() => i + 1,
+            Some(_) => 0,
+            None => 0,
+        };
+        self.list_state.select(Some(next));
+    }
+
+    /// Moves the highlighted selection up, wrapping to the bottom.
+    pub fn select_previous(&mut self) {
+        if self.pairs.is_empty() {
+            return;
+        }
+        let previous = match self.list_state.selected() {
+            Some(0) | None => self.pairs.len() - 1,
+            Some(i) => i - 1,
+        };
+        self.list_state.select(Some(previous));
+    }
+
+    /// Removes the selected pair, if any, and settles the selection on a
+    /// neighbor so repeated `d` presses walk down the list.
+    pub fn delete_selected(&mut self) {
+        let Some(index) = self.list_state.selected() else {
+            return;
+        };
+        if index >= self.pairs.len() {
+            return;
+        }
+        self.pairs.shift_remove_index(index);
+
+        if self.pairs.is_empty() {
+            self.list_state.select(None);
+        } else {
+            self.list_state.select(Some(index.min(self.pairs.len() - 1)));
+        }
+    }
+
+    pub fn toggle_editing(&mut self) {
+        if let Some(edit_mode) = &self.currently_editing {
+            match edit_mode {
+                CurrentlyEditing::Key => self.currently_editing = Some(CurrentlyEditing::Value),
+                CurrentlyEditing::Value => self.currently_editing = Some(CurrentlyEditing::Key),
+            }
+        } else {
+            self.currently_editing = Some(CurrentlyEditing::Key);
+        }
+        self.cursor_position = self.active_input_len();
+    }
+
+    /// The field currently being typed into, or `None` on the Main/Exiting
+    /// screens where no field has focus.
+    fn active_input_mut(&mut self) -> Option<&mut String> {
+        match self.currently_editing {
+            Some(CurrentlyEditing::Key) => Some(&mut self.key_input),
+            Some(CurrentlyEditing::Value) => Some(&mut self.value_input),
+            None => None,
+        }
+    }
+
+    fn active_input(&self) -> Option<&String> {
+        match self.currently_editing {
+            Some(CurrentlyEditing::Key) => Some(&self.key_input),
+            Some(CurrentlyEditing::Value) => Some(&self.value_input),
+            None => None,
+        }
+    }
+
+    /// Length, in grapheme clusters, of the currently active input field.
+    fn active_input_len(&self) -> usize {
+        self.active_input()
+            .map(|input| input.graphemes(true).count())
+            .unwrap_or(0)
+    }
+
+    /// Converts the grapheme-cluster `cursor_position` into the byte offset
+    /// `str::insert`/slicing need.
+    fn byte_index(&self) -> usize {
+        let Some(input) = self.active_input() else {
+            return 0;
+        };
+        input
+            .grapheme_indices(true)
+            .nth(self.cursor_position)
+            .map(|(byte_index, _)| byte_index)
+            .unwrap_or(input.len())
+    }
+
+    pub fn move_cursor_left(&mut self) {
+        self.cursor_position = self.cursor_position.saturating_sub(1);
+    }
+
+    pub fn move_cursor_right(&mut self) {
+        self.cursor_position = (self.cursor_position + 1).min(self.active_input_len());
+    }
+
+    pub fn move_cursor_home(&mut self) {
+        self.cursor_position = 0;
+    }
+
+    pub fn move_cursor_end(&mut self) {
+        self.cursor_position = self.active_input_len();
+    }
+
+    /// Inserts `new_char` at the cursor, then advances the cursor past it.
+    pub fn enter_char(&mut self, new_char: char) {
+        let index = self.byte_index();
+        if let Some(input) = self.active_input_mut() {
+            input.insert(index, new_char);
+        }
+        self.move_cursor_right();
+        self.revalidate_if_editing_value();
+    }
+
+    /// Removes the grapheme cluster before the cursor (Backspace).
+    pub fn delete_char_before(&mut self) {
+        if self.cursor_position == 0 {
+            return;
+        }
+        let from = self.cursor_position - 1;
+        self.remove_grapheme_range(from, self.cursor_position);
+        self.cursor_position = from;
+        self.revalidate_if_editing_value();
+    }
+
+    /// Removes the grapheme cluster after the cursor (Delete).
+    pub fn delete_char_after(&mut self) {
+        let len = self.active_input_len();
+        if self.cursor_position >= len {
+            return;
+        }
+        self.remove_grapheme_range(self.cursor_position, self.cursor_position + 1);
+        self.revalidate_if_editing_value();
+    }
+
+    fn revalidate_if_editing_value(&mut self) {
+        if matches!(self.currently_editing, Some(CurrentlyEditing::Value)) {
+            self.revalidate_value();
+        }
+    }
+
+    fn remove_grapheme_range(&mut self, from: usize, to: usize) {
+        let Some(input) = self.active_input() else {
+            return;
+        };
+        let before: String = input.graphemes(true).take(from).collect();
+        let after: String = input.graphemes(true).skip(to).collect();
+        if let Some(input) = self.active_input_mut() {
+            *input = before + &after;
+        }
+    }
+
+    pub fn print_json(&self) -> serde_json::Result<()> {
+        let output = serde_json::to_string(&self.pairs)?;
+        println!("{output}");
+        Ok(())
+    }
+}
@@ -3,7 +3,7 @@ mod ui;
 
 use crate::ui::ui;
 use app::{App, CurrentScreen, CurrentlyEditing};
-use crossterm::event::{self, DisableMouseCapture, Event, KeyCode, KeyEventKind};
+use crossterm::event::{self, DisableMouseCapture, Event, KeyCode, KeyEventKind, KeyModifiers};
 use crossterm::terminal::{LeaveAlternateScreen, disable_raw_mode};
 use ratatui::Terminal;
 use ratatui::crossterm::event::EnableMouseCapture;
@@ -14,29 +14,22 @@ use std::error::Error;
 use std::io;
 
 fn main() -> Result<(), Box<dyn Error>> {
-    enable_raw_mode()?;
-    let mut stderr = io::stderr();
-    // You might notice that we are using stderr for our output.
-    // This is because we want to allow the user to pipe their
-    // completed json to other programs like ratatui-tutorial > output.json.
-    // To do this, we are using the fact that stderr is
-    // piped differently than stdout. We render output to stderr,
-    // and print our completed json to stdout.
-    execute!(stderr, EnterAlternateScreen, EnableMouseCapture)?;
+    // Install a panic hook before anything touches the terminal. If `run_app`
+    // or `ui` panics, the default hook would otherwise print the backtrace
+    // straight into the alternate screen while we're still in raw mode,
+    // leaving the user's terminal unusable. Restoring first means the
+    // backtrace prints somewhere readable.
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |panic_info| {
+        let _ = restore_terminal();
+        default_hook(panic_info);
+    }));
 
-    let backend = CrosstermBackend::new(stderr);
-    let mut terminal = Terminal::new(backend)?;
-
-    let mut app = App::init();
+    let mut terminal = init_terminal()?;
+    let mut app = load_app(std::env::args().nth(1));
     let res = run_app(&mut terminal, &mut app);
 
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+    restore_terminal()?;
 
     // When an application exits without running this closing boilerplate,
     // the terminal will act very strange, and the user will usually have to
@@ -54,6 +47,66 @@ fn main() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+/// Enables raw mode, enters the alternate screen, and enables mouse capture,
+/// returning a `Terminal` ready for `run_app` to draw into.
+///
+/// You might notice that we are using stderr for our output.
+/// This is because we want to allow the user to pipe their
+/// completed json to other programs like jute > output.json.
+/// To do this, we are using the fact that stderr is
+/// piped differently than stdout. We render output to stderr,
+/// and print our completed json to stdout.
+fn init_terminal() -> io::Result<Terminal<CrosstermBackend<io::Stderr>>> {
+    enable_raw_mode()?;
+    let mut stderr = io::stderr();
+    execute!(stderr, EnterAlternateScreen, EnableMouseCapture)?;
+
+    let backend = CrosstermBackend::new(stderr);
+    Terminal::new(backend)
+}
+
+/// Builds the starting `App` from an optional path given as the first CLI
+/// argument, e.g. `jute config.json`. A missing file starts blank so
+/// `jute new.json > new.json` works the first time; a file that exists but
+/// fails to parse as a JSON object is surfaced on the Error screen instead
+/// of panicking, so `jute config.json > config.json` is a safe edit-in-place
+/// round trip.
+fn load_app(path: Option<String>) -> App {
+    let Some(path) = path else {
+        return App::init();
+    };
+
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return App::init(),
+        Err(err) => return App::with_error(format!("couldn't read {path}: {err}")),
+    };
+
+    if contents.trim().is_empty() {
+        return App::init();
+    }
+
+    match serde_json::from_str(&contents) {
+        Ok(pairs) => App::with_pairs(pairs),
+        Err(err) => App::with_error(format!("couldn't parse {path} as JSON: {err}")),
+    }
+}
+
+/// Undoes everything `init_terminal` did. Safe to call more than once (e.g.
+/// once from the panic hook and once more from `main`'s normal teardown)
+/// since each step is independently idempotent-ish on failure and we only
+/// care about best-effort restoration.
+fn restore_terminal() -> io::Result<()> {
+    let raw_mode = disable_raw_mode();
+    let screen = execute!(
+        io::stderr(),
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        crossterm::cursor::Show
+    );
+    raw_mode.and(screen)
+}
+
 fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<bool> {
     loop {
         // `terminal` is the `Terminal<Backend>` that we take as an argument,
@@ -70,13 +123,24 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<
             }
             match app.current_screen {
                 CurrentScreen::Main => match key.code {
-                    // In this case, `KeyCode::Char('e')` changes the current screen to
-                    // `CurrentScreen::Editing` and sets the `CurrentlyEditing` to a Some and
-                    // notes that the user should be editing the `Key` value field, as opposed
-                    // to the `Value` field.
-                    KeyCode::Char('e') => {
-                        app.current_screen = CurrentScreen::Editing;
-                        app.currently_editing = Some(CurrentlyEditing::Key);
+                    // `e` or Enter edits the highlighted pair if one is
+                    // selected, pre-filling the popup so saving overwrites
+                    // it; with nothing selected it behaves like `a` below.
+                    KeyCode::Char('e') | KeyCode::Enter => {
+                        app.begin_edit_selected();
+                    }
+                    // `a` always starts a blank pair, regardless of selection.
+                    KeyCode::Char('a') => {
+                        app.begin_new_pair();
+                    }
+                    KeyCode::Char('d') => {
+                        app.delete_selected();
+                    }
+                    KeyCode::Char('j') | KeyCode::Down => {
+                        app.select_next();
+                    }
+                    KeyCode::Char('k') | KeyCode::Up => {
+                        app.select_previous();
                     }
                     KeyCode::Char('q') => {
                         app.current_screen = CurrentScreen::Exiting;
@@ -92,6 +156,9 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<
                     }
                     _ => {}
                 },
+                // Nothing to edit if the document failed to load; any key
+                // exits without printing anything.
+                CurrentScreen::Error => return Ok(false),
                 CurrentScreen::Editing if key.kind == KeyEventKind::Press => match key.code {
                     // We would like the Enter key to serve two purposes.
                     // When the user is editing the `Key`, we want the enter key to switch the
@@ -102,29 +169,48 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<
                         if let Some(editing) = &app.currently_editing {
                             match editing {
                                 CurrentlyEditing::Key => {
-                                    app.currently_editing = Some(CurrentlyEditing::Value);
+                                    // `toggle_editing` also resets the cursor to the end
+                                    // of the Value field; without it the cursor carried
+                                    // over the Key field's (often longer) position,
+                                    // breaking Backspace/Left/Delete on first use.
+                                    app.toggle_editing();
                                 }
                                 CurrentlyEditing::Value => {
-                                    app.save_key_value();
-                                    app.current_screen = CurrentScreen::Main;
+                                    // `save_key_value` refuses to save (and
+                                    // leaves `value_error` set for the
+                                    // footer to display) if the text
+                                    // doesn't parse as the active value type.
+                                    if app.save_key_value() {
+                                        app.current_screen = CurrentScreen::Main;
+                                    }
                                 }
                             }
                         }
                     }
-                    // When Backspace is pressed, we need to first determine if the user is
-                    // editing a `Key` or a `Value`, then `pop()` the endings of those strings
-                    // accordingly.
+                    // Cycles the Value field's type (string/number/boolean/null/raw json).
+                    KeyCode::Char('t') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        app.cycle_value_type();
+                    }
+                    // Backspace/Delete remove the grapheme cluster before/after the cursor,
+                    // so multi-codepoint emoji are removed as a single unit rather than
+                    // leaving behind orphaned combining codepoints.
                     KeyCode::Backspace => {
-                        if let Some(editing) = &app.currently_editing {
-                            match editing {
-                                CurrentlyEditing::Key => {
-                                    app.key_input.pop();
-                                }
-                                CurrentlyEditing::Value => {
-                                    app.value_input.pop();
-                                }
-                            }
-                        }
+                        app.delete_char_before();
+                    }
+                    KeyCode::Delete => {
+                        app.delete_char_after();
+                    }
+                    KeyCode::Left => {
+                        app.move_cursor_left();
+                    }
+                    KeyCode::Right => {
+                        app.move_cursor_right();
+                    }
+                    KeyCode::Home => {
+                        app.move_cursor_home();
+                    }
+                    KeyCode::End => {
+                        app.move_cursor_end();
                     }
                     KeyCode::Esc => {
                         app.current_screen = CurrentScreen::Main;
@@ -134,19 +220,10 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<
                     KeyCode::Tab => {
                         app.toggle_editing();
                     }
-                    // if the user types a valid character, we want to capture that, and add it
-                    // to the string that is the final key or value.
+                    // if the user types a valid character, we want to splice it into the
+                    // key or value at the cursor, rather than only ever appending.
                     KeyCode::Char(value) => {
-                        if let Some(editing) = &app.currently_editing {
-                            match editing {
-                                CurrentlyEditing::Key => {
-                                    app.key_input.push(value);
-                                }
-                                CurrentlyEditing::Value => {
-                                    app.value_input.push(value);
-                                }
-                            }
-                        }
+                        app.enter_char(value);
                     }
                     _ => {}
                 },
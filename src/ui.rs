@@ -1,15 +1,17 @@
 use ratatui::{
     Frame,
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Position, Rect},
     style::{Color, Style, Stylize},
     symbols,
     text::{Line, Span, Text},
     widgets::{Block, Borders, Clear, List, ListItem, Padding, Paragraph, Wrap},
 };
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
 use crate::app::{App, CurrentScreen, CurrentlyEditing};
 
-pub fn ui(frame: &mut Frame, app: &App) {
+pub fn ui(frame: &mut Frame, app: &mut App) {
     // Widgets are constructed and then drawn onto the screen using a `Frame`, which is placed
     // within a specified `Rect`. Now, envision a scenario where we wish to divide our
     // renderable `Rect` area into three distinct areas. For this, we can use the `Layout`
@@ -66,16 +68,18 @@ pub fn ui(frame: &mut Frame, app: &App) {
     // selecting items on the list with little extra work.
     let mut list_items = Vec::<ListItem>::new();
 
-    for key in app.pairs.keys() {
+    for (key, value) in &app.pairs {
         list_items.push(ListItem::new(Line::from(Span::styled(
-            format!("{: <25} : {}", key, app.pairs.get(key).unwrap()),
+            format!("{key: <25} : {value}"),
             Style::default().fg(Color::Yellow),
         ))));
     }
 
-    let list = List::new(list_items).style(Style::default().bg(Color::Rgb(15, 15, 15)));
+    let list = List::new(list_items)
+        .style(Style::default().bg(Color::Rgb(15, 15, 15)))
+        .highlight_style(Style::default().bg(Color::Rgb(40, 40, 40)).fg(Color::White));
 
-    frame.render_widget(list, chunks[1]);
+    frame.render_stateful_widget(list, chunks[1], &mut app.list_state);
 
     // Bottom Navbar
     // Two bars, and another layout.
@@ -91,6 +95,7 @@ pub fn ui(frame: &mut Frame, app: &App) {
                 Span::styled("Editing Mode", Style::default().fg(Color::Yellow))
             }
             CurrentScreen::Exiting => Span::styled("Exiting", Style::default().fg(Color::LightRed)),
+            CurrentScreen::Error => Span::styled("Error", Style::default().fg(Color::LightRed)),
         }
         .to_owned(),
         // A white divider bar to separate the two sections
@@ -119,17 +124,20 @@ pub fn ui(frame: &mut Frame, app: &App) {
     let current_keys_hint = {
         match app.current_screen {
             CurrentScreen::Main => Span::styled(
-                "(q) to quit / (e) to make new pair",
+                "(q) quit / (a) new pair / (j/k) select / (e) edit / (d) delete",
                 Style::default().fg(Color::Red),
             ),
             CurrentScreen::Editing => Span::styled(
-                "(ESC) to cancel / (Tab) to switch boxes / (ENTER) to complete",
+                "(ESC) cancel / (Tab) switch boxes / (Ctrl+T) cycle value type / (ENTER) complete",
                 Style::default().fg(Color::Red),
             ),
             CurrentScreen::Exiting => Span::styled(
                 "(q) to quit / (e) to make new pair",
                 Style::default().fg(Color::Red),
             ),
+            CurrentScreen::Error => {
+                Span::styled("(any key) to exit", Style::default().fg(Color::Red))
+            }
         }
     };
 
@@ -159,18 +167,28 @@ pub fn ui(frame: &mut Frame, app: &App) {
             .borders(Borders::NONE)
             .style(Style::default().bg(Color::DarkGray));
 
-        let area = centered_rect(60, 25, frame.area());
+        let area = centered_rect(60, 30, frame.area());
         frame.render_widget(popup_block, area);
 
+        // Split the popup into the key/value row and a one-line footer
+        // beneath it for the active value type and any validation error.
+        // This is the layout built from `centered_rect`'s `Rect` and the one
+        // carrying the `margin(1)` described below; `popup_chunks` then
+        // splits its top row.
+        let popup_rows = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([Constraint::Min(3), Constraint::Length(1)])
+            .split(area);
+
         // create split the `Rect` given to us by `centered_rect`, and create a layout from it.
         // Note the use of `margin(1)`, which gives a 1 space margin around any layout block,
         // meaning our new blocks and widgets donâ€™t overwrite anything
         // from the first popup block.
         let popup_chunks = Layout::default()
             .direction(Direction::Horizontal)
-            .margin(1)
             .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-            .split(area);
+            .split(popup_rows[0]);
 
         // what to display
         let mut key_block = Block::default()
@@ -202,6 +220,37 @@ pub fn ui(frame: &mut Frame, app: &App) {
         // Then we create the `Paragraph` widgets, and assign the blocks
         // with those variables. Also note how we used the `popup_chunks` layout
         // instead of the `popup_block` layout to render these widgets into.
+
+        // Place the terminal's cursor inside whichever field is active. We
+        // measure *display width* up to the cursor rather than counting
+        // chars or graphemes, since wide glyphs (CJK, etc.) occupy two
+        // cells and a naive count would drift the caret to the right of
+        // where the next character is actually typed.
+        let (active_area, active_input) = match editing {
+            CurrentlyEditing::Key => (popup_chunks[0], &app.key_input),
+            CurrentlyEditing::Value => (popup_chunks[1], &app.value_input),
+        };
+        let prefix: String = active_input
+            .graphemes(true)
+            .take(app.cursor_position)
+            .collect();
+        let cursor_x = active_area.x + 1 + prefix.width() as u16;
+        let cursor_y = active_area.y + 1;
+        frame.set_cursor_position(Position::new(cursor_x, cursor_y));
+
+        // Footer: the active value type, plus any validation error for the
+        // text currently in the Value field.
+        let type_footer = match &app.value_error {
+            Some(error) => Span::styled(
+                format!("value: {} (Ctrl+T to change) - {error}", app.value_type.label()),
+                Style::default().fg(Color::LightRed),
+            ),
+            None => Span::styled(
+                format!("value: {} (Ctrl+T to change)", app.value_type.label()),
+                Style::default().fg(Color::Gray),
+            ),
+        };
+        frame.render_widget(Paragraph::new(Line::from(type_footer)), popup_rows[1]);
     }
 
     // In this screen, we are asking the user if they want to output
@@ -231,6 +280,37 @@ pub fn ui(frame: &mut Frame, app: &App) {
         let area = centered_rect(60, 25, frame.area());
         frame.render_widget(exit_paragraph, area);
     }
+
+    // Shown in place of the Main screen when the file given on the command
+    // line couldn't be loaded, so a bad path surfaces as a readable message
+    // instead of a panic.
+    if let CurrentScreen::Error = app.current_screen {
+        frame.render_widget(Clear, frame.area());
+
+        let popup_block = Block::bordered()
+            .title("Error")
+            .title_style(Style::default().bold())
+            .title_alignment(Alignment::Center)
+            .border_set(symbols::border::ROUNDED)
+            .style(Style::new().fg(Color::White).bg(Color::Rgb(123, 3, 35)))
+            .padding(Padding::uniform(2));
+
+        let message = app
+            .error_message
+            .as_deref()
+            .unwrap_or("an unknown error occurred");
+        let error_text = Text::styled(
+            format!("{message}\n\npress any key to exit"),
+            Style::default().fg(Color::White),
+        );
+
+        let error_paragraph = Paragraph::new(error_text)
+            .block(popup_block)
+            .wrap(Wrap { trim: false });
+
+        let area = centered_rect(60, 25, frame.area());
+        frame.render_widget(error_paragraph, area);
+    }
 }
 
 /// helper function to create a centered rect using up certain